@@ -0,0 +1,208 @@
+//! Randomized-batch pairing verification backing `verifyProofsBatch`.
+//!
+//! `oberon::Proof::open` checks one proof at a time behind an opaque, all-or-
+//! nothing pairing equality, and `oberon` exposes no batch entry point or
+//! accessor to the G1/G2 terms underneath it - `Proof` and `PublicKey`'s fields
+//! are `pub(crate)`. To fold many proofs into a single aggregate Miller loop,
+//! this module reconstructs those terms itself straight from each artifact's
+//! public byte encoding, duplicating `oberon`'s own `hash_to_scalar`/
+//! `hash_to_curve` (see its `util.rs`) and the equation `Proof::open` checks
+//! (see its `proof.rs`) against `bls12_381_plus` directly - an independent
+//! BLS12-381 backend from whichever one `oberon` itself is built against
+//! (`blstrs_plus`/`blst`, since this crate's "neon" feature enables oberon's
+//! "std"), which has to stay byte/value-compatible with it for the aggregate
+//! check to agree with `Proof::open`. A future `oberon` release that changes
+//! either of those needs a matching change here.
+
+use bls12_381_plus::ff::Field;
+use bls12_381_plus::group::{Curve, Group};
+use bls12_381_plus::elliptic_curve::hash2curve::ExpandMsgXof;
+use bls12_381_plus::{multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar};
+use digest::{ExtendableOutput, Update, XofReader};
+use rand::rngs::OsRng;
+use rand::RngCore;
+use sha3::Shake256;
+use std::convert::TryFrom;
+
+use crate::core::CoreError;
+
+const TO_SCALAR_DST: &[u8] = b"OBERON_BLS12381FQ_XOF:SHAKE-256_";
+const TO_CURVE_DST: &[u8] = b"OBERON_BLS12381G1_XOF:SHAKE-256_SSWU_RO_";
+
+/// One batch item's `(proof, publicKey, id, nonce)` bytes, borrowed from the caller.
+pub type BatchItem<'a> = (&'a [u8], &'a [u8], &'a [u8], &'a [u8]);
+
+fn hash_to_scalar(data: &[&[u8]]) -> Scalar {
+    let mut hasher = Shake256::default();
+    hasher.update(TO_SCALAR_DST);
+    for slice in data {
+        hasher.update(slice);
+    }
+    let mut reader = hasher.finalize_xof();
+    let mut buf = [0u8; 48];
+    reader.read(&mut buf);
+    Scalar::from_okm(&buf)
+}
+
+fn hash_to_curve(data: &[u8]) -> G1Projective {
+    G1Projective::hash::<ExpandMsgXof<Shake256>>(data, TO_CURVE_DST)
+}
+
+fn random_nonzero_scalar(rng: &mut impl RngCore) -> Scalar {
+    loop {
+        let s = Scalar::random(&mut *rng);
+        if !bool::from(s.is_zero()) {
+            return s;
+        }
+    }
+}
+
+/// The two pairing terms `Proof::open` checks for one item: `e(u', rhs) . e(z, G) == 1`.
+struct ItemTerms {
+    u_prime: G1Projective,
+    rhs: G2Projective,
+    z: G1Projective,
+}
+
+fn item_terms(proof_bytes: &[u8], pk_bytes: &[u8], id_bytes: &[u8], nonce_bytes: &[u8]) -> Result<ItemTerms, CoreError> {
+    if proof_bytes.len() != oberon::Proof::BYTES || pk_bytes.len() != oberon::PublicKey::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+
+    let u: G1Projective = Option::from(
+        G1Affine::from_compressed(&<[u8; 48]>::try_from(&proof_bytes[..48]).unwrap()).map(G1Projective::from),
+    )
+    .ok_or(CoreError::InvalidInput)?;
+    let z: G1Projective = Option::from(
+        G1Affine::from_compressed(&<[u8; 48]>::try_from(&proof_bytes[48..]).unwrap()).map(G1Projective::from),
+    )
+    .ok_or(CoreError::InvalidInput)?;
+
+    let w: G2Projective = Option::from(
+        G2Affine::from_compressed(&<[u8; 96]>::try_from(&pk_bytes[0..96]).unwrap()).map(G2Projective::from),
+    )
+    .ok_or(CoreError::InvalidInput)?;
+    let x: G2Projective = Option::from(
+        G2Affine::from_compressed(&<[u8; 96]>::try_from(&pk_bytes[96..192]).unwrap()).map(G2Projective::from),
+    )
+    .ok_or(CoreError::InvalidInput)?;
+    let y: G2Projective = Option::from(
+        G2Affine::from_compressed(&<[u8; 96]>::try_from(&pk_bytes[192..288]).unwrap()).map(G2Projective::from),
+    )
+    .ok_or(CoreError::InvalidInput)?;
+
+    if bool::from(u.is_identity())
+        || bool::from(z.is_identity())
+        || bool::from(w.is_identity())
+        || bool::from(x.is_identity())
+        || bool::from(y.is_identity())
+    {
+        return Err(CoreError::InvalidInput);
+    }
+
+    let m = hash_to_scalar(&[id_bytes]);
+    if bool::from(m.is_zero()) {
+        return Err(CoreError::InvalidInput);
+    }
+    let m_tick = hash_to_scalar(&[&m.to_le_bytes()[..]]);
+    if bool::from(m_tick.is_zero()) {
+        return Err(CoreError::InvalidInput);
+    }
+    let a = hash_to_curve(&m_tick.to_le_bytes()[..]);
+    if bool::from(a.is_identity()) {
+        return Err(CoreError::InvalidInput);
+    }
+
+    let t = hash_to_scalar(&[&u.to_affine().to_compressed(), nonce_bytes]);
+    let u_prime = a * t + u;
+    let rhs = w * m_tick + x + y * m;
+
+    Ok(ItemTerms { u_prime, rhs, z })
+}
+
+/// Checks whether every `(proof, publicKey, id, nonce)` item verifies, via one
+/// aggregate multi-Miller-loop rather than a pairing per item.
+///
+/// Folds each item's pairing equality `e(u'_i, rhs_i) . e(z_i, G) == 1` into the
+/// single equation `∏_i e(δ_i.u'_i, rhs_i) . e(δ_i.z_i, G) == 1`, with a fresh
+/// random nonzero δ_i per item drawn from `OsRng` (δ_0 fixed at 1). The random
+/// weights are essential: without them, one invalid proof's failure can cancel
+/// against another invalid item's in the product, letting a crafted pair of
+/// forged proofs slip through together.
+///
+/// A malformed item (wrong lengths, or bytes that don't decode to a point on
+/// the curve) makes this return `Err` before any pairing is computed, while a
+/// well-formed but cryptographically invalid batch returns `Ok(false)`. Either
+/// way the caller is expected to fall back to per-item verification to find
+/// out which index(es) actually failed.
+pub fn verify_aggregate(items: &[BatchItem]) -> Result<bool, CoreError> {
+    if items.is_empty() {
+        return Ok(true);
+    }
+
+    let mut terms = Vec::with_capacity(items.len());
+    for &(proof, pk, id, nonce) in items {
+        terms.push(item_terms(proof, pk, id, nonce)?);
+    }
+
+    let mut rng = OsRng;
+    let g2_prepared_generator = G2Prepared::from(G2Affine::generator());
+    let mut miller_terms: Vec<(G1Affine, G2Prepared)> = Vec::with_capacity(terms.len() * 2);
+    for (i, term) in terms.into_iter().enumerate() {
+        let delta = if i == 0 { Scalar::ONE } else { random_nonzero_scalar(&mut rng) };
+        miller_terms.push(((term.u_prime * delta).to_affine(), G2Prepared::from(term.rhs.to_affine())));
+        miller_terms.push(((term.z * delta).to_affine(), g2_prepared_generator.clone()));
+    }
+
+    let refs: Vec<(&G1Affine, &G2Prepared)> = miller_terms.iter().map(|(a, b)| (a, b)).collect();
+    Ok(bool::from(multi_miller_loop(&refs).final_exponentiation().is_identity()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use oberon::{PublicKey, SecretKey, Token};
+    use rand::rngs::OsRng as RandOsRng;
+
+    fn valid_item(id: &'static [u8], nonce: &'static [u8]) -> (Vec<u8>, Vec<u8>, &'static [u8], &'static [u8]) {
+        let sk = SecretKey::new(RandOsRng {});
+        let pk = PublicKey::from(&sk);
+        let token = Token::new(&sk, id).unwrap();
+        let proof = oberon::Proof::new(&token, &[], id, nonce, RandOsRng {}).unwrap();
+        (proof.to_bytes().to_vec(), pk.to_bytes().to_vec(), id, nonce)
+    }
+
+    #[test]
+    fn aggregate_accepts_a_batch_of_valid_proofs() {
+        let items = [
+            valid_item(b"aggregate::tests id one", b"aggregate::tests nonce one"),
+            valid_item(b"aggregate::tests id two", b"aggregate::tests nonce two"),
+            valid_item(b"aggregate::tests id three", b"aggregate::tests nonce three"),
+        ];
+        let borrowed: Vec<BatchItem> = items.iter().map(|(p, pk, id, n)| (p.as_slice(), pk.as_slice(), *id, *n)).collect();
+
+        assert_eq!(verify_aggregate(&borrowed), Ok(true));
+    }
+
+    #[test]
+    fn aggregate_rejects_a_batch_containing_one_invalid_proof() {
+        let mut items = [
+            valid_item(b"aggregate::tests id one", b"aggregate::tests nonce one"),
+            valid_item(b"aggregate::tests id two", b"aggregate::tests nonce two"),
+        ];
+        // Verify against the wrong nonce for the second item - well-formed bytes,
+        // cryptographically invalid, so the aggregate must come out false.
+        items[1].3 = b"an entirely different nonce";
+        let borrowed: Vec<BatchItem> = items.iter().map(|(p, pk, id, n)| (p.as_slice(), pk.as_slice(), *id, *n)).collect();
+
+        assert_eq!(verify_aggregate(&borrowed), Ok(false));
+    }
+
+    #[test]
+    fn aggregate_rejects_malformed_items_by_length() {
+        let items = [(vec![0u8; 10], vec![0u8; oberon::PublicKey::BYTES], &b"id"[..], &b"nonce"[..])];
+        let borrowed: Vec<BatchItem> = items.iter().map(|(p, pk, id, n)| (p.as_slice(), pk.as_slice(), *id, *n)).collect();
+
+        assert_eq!(verify_aggregate(&borrowed), Err(CoreError::InvalidLength));
+    }
+}
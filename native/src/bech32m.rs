@@ -0,0 +1,160 @@
+//! A minimal Bech32m (BIP-350) codec with no payload-length cap.
+//!
+//! The widely-used `bech32` crate enforces BIP-173's 90-character total-length
+//! budget, which exists for human-typed addresses, not for this binding's use -
+//! wrapping arbitrary artifact bytes (a 288-byte `PublicKey`, a multi-element
+//! `Token`/`Proof`) easily exceeds it. The checksum algorithm itself has no such
+//! restriction, so this implements it directly against the BIP-350 reference
+//! pseudocode instead of going through the crate's length-checked `encode`/`decode`.
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+fn polymod(values: &[u8]) -> u32 {
+    const GEN: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut chk: u32 = 1;
+    for &v in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x01ff_ffff) << 5) ^ (v as u32);
+        for (i, gen) in GEN.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= gen;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut v: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    v.push(0);
+    v.extend(hrp.bytes().map(|b| b & 31));
+    v
+}
+
+fn create_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+    let polymod = polymod(&values) ^ BECH32M_CONST;
+
+    let mut checksum = [0u8; 6];
+    for (i, byte) in checksum.iter_mut().enumerate() {
+        *byte = ((polymod >> (5 * (5 - i))) & 31) as u8;
+    }
+    checksum
+}
+
+fn verify_checksum(hrp: &str, data: &[u8]) -> bool {
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(data);
+    polymod(&values) == BECH32M_CONST
+}
+
+/// Regroups bits between two word sizes, e.g. 8-bit bytes <-> 5-bit Bech32 groups.
+fn convert_bits(data: &[u8], from_bits: u32, to_bits: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let maxv: u32 = (1 << to_bits) - 1;
+    let mut ret = Vec::with_capacity(data.len() * from_bits as usize / to_bits as usize + 1);
+
+    for &value in data {
+        let value = value as u32;
+        if value >> from_bits != 0 {
+            return None;
+        }
+        acc = (acc << from_bits) | value;
+        bits += from_bits;
+        while bits >= to_bits {
+            bits -= to_bits;
+            ret.push(((acc >> bits) & maxv) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            ret.push(((acc << (to_bits - bits)) & maxv) as u8);
+        }
+    } else if bits >= from_bits || ((acc << (to_bits - bits)) & maxv) != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+/// Encodes `data` as a Bech32m string with human-readable prefix `hrp`, with no
+/// cap on `data`'s length.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let data_u5 = convert_bits(data, 8, 5, true).expect("8-to-5 bit conversion with padding cannot fail");
+    let checksum = create_checksum(hrp, &data_u5);
+
+    let mut result = String::with_capacity(hrp.len() + 1 + data_u5.len() + checksum.len());
+    result.push_str(hrp);
+    result.push('1');
+    for &b in data_u5.iter().chain(checksum.iter()) {
+        result.push(CHARSET[b as usize] as char);
+    }
+    result
+}
+
+/// Decodes a Bech32m string produced by `encode`, returning its human-readable
+/// prefix and raw payload bytes.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>), &'static str> {
+    if s.chars().any(|c| c.is_ascii_uppercase()) && s.chars().any(|c| c.is_ascii_lowercase()) {
+        return Err("string contains mixed-case characters");
+    }
+    let s = s.to_ascii_lowercase();
+
+    let sep = s.rfind('1').ok_or("missing '1' separator")?;
+    if sep == 0 || sep + 7 > s.len() {
+        return Err("separator is misplaced or checksum is too short");
+    }
+    let hrp = s[..sep].to_string();
+
+    let mut data = Vec::with_capacity(s.len() - sep - 1);
+    for c in s[sep + 1..].chars() {
+        let v = CHARSET.iter().position(|&x| x as char == c).ok_or("invalid character in data part")?;
+        data.push(v as u8);
+    }
+
+    if !verify_checksum(&hrp, &data) {
+        return Err("invalid checksum");
+    }
+
+    let payload = &data[..data.len() - 6];
+    let bytes = convert_bits(payload, 5, 8, false).ok_or("payload has non-zero padding bits")?;
+    Ok((hrp, bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_arbitrary_length_payloads() {
+        for len in [0usize, 1, 48, 96, 288] {
+            let data: Vec<u8> = (0..len).map(|i| (i % 256) as u8).collect();
+            let encoded = encode("otok", &data);
+            let (hrp, decoded) = decode(&encoded).expect("round-trip decode should succeed");
+            assert_eq!(hrp, "otok");
+            assert_eq!(decoded, data);
+        }
+    }
+
+    #[test]
+    fn rejects_mixed_case() {
+        let encoded = encode("opk", &[1, 2, 3]);
+        let mut mixed = encoded.clone();
+        let upper_idx = mixed.find('1').unwrap() + 1;
+        mixed.replace_range(upper_idx..upper_idx + 1, &mixed[upper_idx..upper_idx + 1].to_ascii_uppercase());
+        assert!(decode(&mixed).is_err());
+    }
+
+    #[test]
+    fn rejects_corrupted_checksum() {
+        let mut encoded = encode("oproof", &[0xde, 0xad, 0xbe, 0xef]);
+        let last = encoded.pop().unwrap();
+        let replacement = CHARSET.iter().map(|&b| b as char).find(|&c| c != last).unwrap();
+        encoded.push(replacement);
+        assert_eq!(decode(&encoded), Err("invalid checksum"));
+    }
+}
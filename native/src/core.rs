@@ -0,0 +1,128 @@
+//! Pure Oberon token-scheme logic shared by every language binding (the Neon
+//! native addon and the `wasm-bindgen` build). Nothing in this module knows
+//! about Neon, `wasm-bindgen`, or any other host runtime - it only turns byte
+//! slices into `oberon` types and back, so each binding can wrap it in
+//! whatever argument/error conventions its host expects.
+
+use oberon::*;
+use rand::rngs::OsRng;
+use std::convert::TryFrom;
+
+/// An error from the core logic, independent of any host binding. Bindings
+/// translate this into their own error type (a Neon `Throw`, a wasm `JsError`, ...).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CoreError {
+    /// An argument was not the exact byte length the artifact requires.
+    InvalidLength,
+    /// The bytes had the right length but did not decode to a valid artifact,
+    /// or `Token::new`/`Proof::new` returned `None`. A proof that decodes but
+    /// fails to open is not an error; `verify_proof` returns `Ok(false)` for it.
+    InvalidInput,
+}
+
+pub fn generate_keys(seed: Option<&[u8]>) -> (Vec<u8>, Vec<u8>) {
+    let sk = match seed {
+        Some(seed) => SecretKey::hash(seed),
+        None => SecretKey::new(OsRng {}),
+    };
+    let pk = PublicKey::from(&sk);
+
+    (sk.to_bytes().to_vec(), pk.to_bytes().to_vec())
+}
+
+pub fn generate_blinding(blinding_data: &[u8]) -> Vec<u8> {
+    Blinding::new(blinding_data).to_bytes().to_vec()
+}
+
+pub fn decode_blinding(blinding_bytes: &[u8]) -> Result<Blinding, CoreError> {
+    if blinding_bytes.len() != Blinding::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+    let blinding: Option<Blinding> =
+        Blinding::from_bytes(&<[u8; Blinding::BYTES]>::try_from(blinding_bytes).unwrap()).into();
+    blinding.ok_or(CoreError::InvalidInput)
+}
+
+pub fn build_token(id_bytes: &[u8], sk_bytes: &[u8]) -> Result<Token, CoreError> {
+    if sk_bytes.len() != SecretKey::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+
+    let sk: Option<SecretKey> =
+        SecretKey::from_bytes(&<[u8; SecretKey::BYTES]>::try_from(sk_bytes).unwrap()).into();
+    let sk = sk.ok_or(CoreError::InvalidInput)?;
+    Token::new(&sk, id_bytes).ok_or(CoreError::InvalidInput)
+}
+
+pub fn blind_token(token_bytes: &[u8], blinding_bytes: &[u8]) -> Result<Vec<u8>, CoreError> {
+    if token_bytes.len() != Token::BYTES || blinding_bytes.len() != Blinding::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+
+    let token: Option<Token> =
+        Token::from_bytes(&<[u8; Token::BYTES]>::try_from(token_bytes).unwrap()).into();
+    let token = token.ok_or(CoreError::InvalidInput)?;
+    let blinding = decode_blinding(blinding_bytes)?;
+
+    Ok((token - blinding).to_bytes().to_vec())
+}
+
+/// Builds a proof for `token_bytes` bound to `nonce_bytes`, the shared core of
+/// every binding's `newProof` and of the Neon addon's `newProofTimestamp`.
+pub fn build_proof(token_bytes: &[u8], blindings: &[Blinding], id_bytes: &[u8], nonce_bytes: &[u8]) -> Result<Proof, CoreError> {
+    if token_bytes.len() != Token::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+    let token: Option<Token> =
+        Token::from_bytes(&<[u8; Token::BYTES]>::try_from(token_bytes).unwrap()).into();
+    let token = token.ok_or(CoreError::InvalidInput)?;
+
+    Proof::new(&token, blindings, id_bytes, nonce_bytes, OsRng {}).ok_or(CoreError::InvalidInput)
+}
+
+/// Decodes and checks a proof against a public key, id, and nonce.
+/// Malformed input (wrong lengths, unparseable bytes) is reported as `CoreError`;
+/// a well-formed but cryptographically invalid proof simply yields `false`.
+pub fn verify_proof(proof_bytes: &[u8], pk_bytes: &[u8], id_bytes: &[u8], nonce_bytes: &[u8]) -> Result<bool, CoreError> {
+    if proof_bytes.len() != Proof::BYTES || pk_bytes.len() != PublicKey::BYTES {
+        return Err(CoreError::InvalidLength);
+    }
+
+    let proof: Option<Proof> = Proof::from_bytes(&<[u8; Proof::BYTES]>::try_from(proof_bytes).unwrap()).into();
+    let proof = proof.ok_or(CoreError::InvalidInput)?;
+    let pk: Option<PublicKey> = PublicKey::from_bytes(&<[u8; PublicKey::BYTES]>::try_from(pk_bytes).unwrap()).into();
+    let pk = pk.ok_or(CoreError::InvalidInput)?;
+
+    Ok(proof.open(pk, id_bytes, nonce_bytes).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_proof_accepts_a_valid_proof_and_rejects_a_tampered_one() {
+        let sk = SecretKey::new(OsRng {});
+        let pk = PublicKey::from(&sk);
+        let id = b"core::tests identity";
+        let nonce = b"core::tests nonce";
+
+        let token = build_token(id, &sk.to_bytes()).unwrap();
+        let proof = build_proof(&token.to_bytes(), &[], id, nonce).unwrap();
+
+        assert_eq!(verify_proof(&proof.to_bytes(), &pk.to_bytes(), id, nonce), Ok(true));
+
+        let wrong_nonce = b"a different nonce entirely";
+        assert_eq!(verify_proof(&proof.to_bytes(), &pk.to_bytes(), id, wrong_nonce), Ok(false));
+    }
+
+    #[test]
+    fn verify_proof_rejects_non_canonical_bytes_instead_of_panicking() {
+        let pk = PublicKey::from(&SecretKey::new(OsRng {}));
+        let bad_proof = [0xffu8; Proof::BYTES];
+        assert_eq!(
+            verify_proof(&bad_proof, &pk.to_bytes(), b"id", b"nonce"),
+            Err(CoreError::InvalidInput)
+        );
+    }
+}
@@ -0,0 +1,109 @@
+use neon::prelude::*;
+use neon::types::buffer::TypedArray;
+use oberon::{PublicKey, SecretKey, Token, Proof};
+
+use crate::bech32m;
+
+const HRP_SECRET_KEY: &str = "osk";
+const HRP_PUBLIC_KEY: &str = "opk";
+const HRP_TOKEN: &str = "otok";
+const HRP_PROOF: &str = "oproof";
+
+fn hrp_for_kind(kind: &str) -> Option<&'static str> {
+    match kind {
+        "secretKey" => Some(HRP_SECRET_KEY),
+        "publicKey" => Some(HRP_PUBLIC_KEY),
+        "token" => Some(HRP_TOKEN),
+        "proof" => Some(HRP_PROOF),
+        _ => None,
+    }
+}
+
+fn kind_for_hrp(hrp: &str) -> Option<&'static str> {
+    match hrp {
+        HRP_SECRET_KEY => Some("secretKey"),
+        HRP_PUBLIC_KEY => Some("publicKey"),
+        HRP_TOKEN => Some("token"),
+        HRP_PROOF => Some("proof"),
+        _ => None,
+    }
+}
+
+fn expected_len(kind: &str) -> Option<usize> {
+    match kind {
+        "secretKey" => Some(SecretKey::BYTES),
+        "publicKey" => Some(PublicKey::BYTES),
+        "token" => Some(Token::BYTES),
+        "proof" => Some(Proof::BYTES),
+        _ => None,
+    }
+}
+
+/// Encodes an artifact's raw bytes as a checksummed, self-describing Bech32m string,
+/// e.g. `opk1...` for a public key, following the HRP-prefixed encoding zcash_address
+/// and similar wallet tooling use. Mixing up artifact kinds now fails loudly instead
+/// of producing an opaque length-mismatch `Throw` on a later call.
+///
+/// Uses `crate::bech32m` rather than the `bech32` crate: the crate enforces BIP-173's
+/// 90-character total-length cap meant for human-typed addresses, which a 288-byte
+/// `PublicKey` blows past well before the HRP and checksum even factor in.
+/// @param string `kind` - One of "secretKey", "publicKey", "token", "proof"
+/// @param ArrayBuffer `bytes` - The raw artifact bytes to encode
+/// @returns string - The Bech32m-encoded artifact
+pub fn encode(mut cx: FunctionContext) -> JsResult<JsString> {
+    let kind = cx.argument::<JsString>(0)?.value(&mut cx);
+    let bytes_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+
+    let hrp = match hrp_for_kind(&kind) {
+        Some(hrp) => hrp,
+        None => return cx.throw_error(format!("unknown artifact kind '{}'", kind)),
+    };
+    let expected = expected_len(&kind).unwrap();
+
+    let bytes = bytes_buffer.as_slice(&cx).to_vec();
+    if bytes.len() != expected {
+        return cx.throw_error(format!(
+            "expected {} bytes for a {}, got {}",
+            expected, kind, bytes.len()
+        ));
+    }
+
+    Ok(cx.string(bech32m::encode(hrp, &bytes)))
+}
+
+/// Decodes a Bech32m string produced by `encode`, returning the artifact kind
+/// alongside its raw bytes. Throws a descriptive error if the string is malformed,
+/// the checksum fails, or the human-readable prefix doesn't match a known kind.
+/// @param string `encoded` - The Bech32m-encoded artifact
+/// @returns {
+///     "kind": string,
+///     "bytes": ArrayBuffer
+/// }
+pub fn decode(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let encoded = cx.argument::<JsString>(0)?.value(&mut cx);
+
+    let (hrp, bytes) = match bech32m::decode(&encoded) {
+        Ok(parts) => parts,
+        Err(e) => return cx.throw_error(format!("invalid bech32m string: {}", e)),
+    };
+
+    let kind = match kind_for_hrp(&hrp) {
+        Some(kind) => kind,
+        None => return cx.throw_error(format!("unrecognized artifact prefix '{}'", hrp)),
+    };
+
+    let expected = expected_len(kind).unwrap();
+    if bytes.len() != expected {
+        return cx.throw_error(format!(
+            "expected {} bytes for a {}, got {}",
+            expected, kind, bytes.len()
+        ));
+    }
+
+    let result = JsObject::new(&mut cx);
+    let kind_str = cx.string(kind);
+    let bytes_buffer = crate::slice_to_js_array_buffer!(&bytes, cx);
+    result.set(&mut cx, "kind", kind_str)?;
+    result.set(&mut cx, "bytes", bytes_buffer)?;
+    Ok(result)
+}
@@ -0,0 +1,449 @@
+use neon::prelude::*;
+use neon::result::Throw;
+use neon::types::buffer::TypedArray;
+use oberon::Blinding;
+use std::convert::TryFrom;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+use threadpool::ThreadPool;
+
+use crate::core::{self, CoreError};
+
+fn to_throw<'a, C: Context<'a>>(cx: &mut C, e: CoreError) -> Throw {
+    cx.throw_error::<_, ()>(format!("{:?}", e)).unwrap_err()
+}
+
+/// The pool the async bindings below offload Oberon's elliptic-curve work to.
+/// neon 0.10's own `Task` type only schedules onto libuv's threadpool under the
+/// `neon-sys` (nan) backend, which this crate doesn't use - this binding is built
+/// on `napi-6`, which has no such built-in. A bare `std::thread::spawn` per call
+/// would work too, but leaves the pool unbounded under load; sized to the
+/// machine's parallelism instead, so a flood of calls queues rather than forking
+/// a thread per request.
+fn background_pool() -> &'static ThreadPool {
+    static POOL: OnceLock<ThreadPool> = OnceLock::new();
+    POOL.get_or_init(|| {
+        let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+        ThreadPool::new(workers)
+    })
+}
+
+#[macro_export]
+macro_rules! slice_to_js_array_buffer {
+    ($slice:expr, $cx:expr) => {{
+        let mut result = JsArrayBuffer::new(&mut $cx, $slice.len())?;
+        result.as_mut_slice(&mut $cx).copy_from_slice($slice);
+        result
+    }};
+}
+
+macro_rules! obj_field_to_vec {
+    ($cx:expr, $field: expr) => {{
+        let v: Vec<Handle<JsValue>> = $cx
+            .argument::<JsArray>($field)?
+            .to_vec(&mut $cx)?;
+        v
+    }};
+}
+
+/// Downcasts and decodes a JS array of blinding `ArrayBuffer`s into `Blinding` values.
+fn decode_blindings(cx: &mut FunctionContext, blindings_vec: Vec<Handle<JsValue>>) -> Result<Vec<Blinding>, Throw> {
+    let mut blindings = Vec::with_capacity(blindings_vec.len());
+    for b in blindings_vec {
+        let a = b.downcast::<JsArrayBuffer, _>(cx).or_throw(cx)?;
+        let blinding_bytes = a.as_slice(cx);
+        blindings.push(core::decode_blinding(blinding_bytes).map_err(|e| to_throw(cx, e))?);
+    }
+    Ok(blindings)
+}
+
+/// @param [opt] ArrayBuffer `seed` - An optional seed to create an oberon key pair
+/// @returns {
+///     "secretKey": ArrayBuffer,
+///     "publicKey": ArrayBuffer
+/// }
+fn new_keys(mut cx: FunctionContext)-> JsResult<JsObject> {
+    let seed = match cx.argument_opt(0) {
+        Some(seed) => {
+            let seed: Handle<JsArrayBuffer> = seed.downcast::<JsArrayBuffer, _>(&mut cx).or_throw(&mut cx)?;
+            Some(seed.as_slice(&cx).to_vec())
+        },
+        None => None,
+    };
+    let (sk_bytes, pk_bytes) = core::generate_keys(seed.as_deref());
+
+    let sk_bytes = slice_to_js_array_buffer!(&sk_bytes, cx);
+    let pk_bytes = slice_to_js_array_buffer!(&pk_bytes, cx);
+
+    let result = JsObject::new(&mut cx);
+    result.set(&mut cx, "secretKey", sk_bytes)?;
+    result.set(&mut cx, "publicKey", pk_bytes)?;
+    Ok(result)
+}
+
+/// @param ArrayBuffer `blinding` - A byte array for a blinding factor
+/// @returns {
+///     "blinding": ArrayBuffer
+/// }
+fn new_blinding(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let blinding: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let blinding_data = blinding.as_slice(&cx);
+    let blinding_bytes = core::generate_blinding(blinding_data);
+
+    let blinding_bytes = slice_to_js_array_buffer!(&blinding_bytes, cx);
+
+    let result = JsObject::new(&mut cx);
+    result.set(&mut cx, "blinding", blinding_bytes)?;
+    Ok(result)
+}
+
+/// @param ArrayBuffer `id` - The identifier to use for this token
+/// @param ArrayBuffer `secretKey` - The secret key used for signing this token
+/// @returns {
+///     "token": ArrayBuffer
+/// }
+fn new_token(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let sk_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+
+    let id_bytes = id_buffer.as_slice(&cx);
+    let sk_bytes = sk_buffer.as_slice(&cx);
+
+    let token = core::build_token(id_bytes, sk_bytes).map_err(|e| to_throw(&mut cx, e))?;
+
+    let token_bytes = slice_to_js_array_buffer!(&token.to_bytes(), cx);
+
+    let result = JsObject::new(&mut cx);
+    result.set(&mut cx, "token", token_bytes)?;
+    Ok(result)
+}
+
+/// @param ArrayBuffer `token` - The token or blinded token signed by the issuing authority
+/// @param ArrayBuffer `blinding` - The blinding factor to apply to the token
+/// @returns {
+///     "token": ArrayBuffer
+/// }
+fn blind_token(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let token_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let blinding_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+
+    let token_bytes = token_buffer.as_slice(&cx);
+    let blinding_bytes = blinding_buffer.as_slice(&cx);
+
+    let blinded_token_bytes = core::blind_token(token_bytes, blinding_bytes).map_err(|e| to_throw(&mut cx, e))?;
+    let blinded_token_bytes = slice_to_js_array_buffer!(&blinded_token_bytes, cx);
+
+    let result = JsObject::new(&mut cx);
+    result.set(&mut cx, "token", blinded_token_bytes)?;
+    Ok(result)
+}
+
+/// Generates a new proof bound to a caller-supplied nonce, e.g. a server-issued
+/// challenge, rather than the wall-clock timestamp `newProofTimestamp` uses.
+/// `newProofTimestamp` is not implemented as a JS-level call into this function;
+/// both are independent Neon bindings over the same `core::build_proof`, since
+/// `newProofTimestamp` also needs to hand the computed timestamp bytes back to
+/// the caller, which this one doesn't compute.
+/// @param ArrayBuffer `token` - The token or blinded token for which to generate a proof
+/// @param ArrayBuffer `id` - The identifier to use for this token
+/// @param Array<ArrayBuffer> `blindings` - All the blindings applied to the token
+/// @param ArrayBuffer `nonce` - The nonce to bind this proof to
+/// @returns {
+///     "proof": ArrayBuffer
+/// }
+fn new_proof(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let token_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let blindings_vec = obj_field_to_vec!(cx, 2);
+    let nonce_buffer: Handle<JsArrayBuffer> = cx.argument(3)?;
+
+    let blindings = decode_blindings(&mut cx, blindings_vec)?;
+
+    let token_bytes = token_buffer.as_slice(&cx);
+    let id_bytes = id_buffer.as_slice(&cx);
+    let nonce_bytes = nonce_buffer.as_slice(&cx);
+
+    let proof = core::build_proof(token_bytes, blindings.as_slice(), id_bytes, nonce_bytes).map_err(|e| to_throw(&mut cx, e))?;
+
+    let result = JsObject::new(&mut cx);
+    let proof_bytes = slice_to_js_array_buffer!(&proof.to_bytes()[..], cx);
+    result.set(&mut cx, "proof", proof_bytes)?;
+    Ok(result)
+}
+
+/// Generates a new proof using the current system timestamp as the nonce
+/// @param ArrayBuffer `token` - The token or blinded token for which to generate a proof
+/// @param ArrayBuffer `id` - The identifier to use for this token
+/// @param Array<ArrayBuffer> `blindings` - All the blindings applied to the token
+/// @returns {
+///     "proof": ArrayBuffer,
+///     "timestamp": ArrayBuffer
+/// }
+fn new_proof_timestamp(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let token_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let blindings_vec = obj_field_to_vec!(cx, 2);
+
+    let blindings = decode_blindings(&mut cx, blindings_vec)?;
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+    let timestamp_bytes = timestamp.as_micros().to_be_bytes();
+
+    let token_bytes = token_buffer.as_slice(&cx);
+    let id_bytes = id_buffer.as_slice(&cx);
+
+    let proof = core::build_proof(token_bytes, blindings.as_slice(), id_bytes, &timestamp_bytes[..]).map_err(|e| to_throw(&mut cx, e))?;
+
+    let result = JsObject::new(&mut cx);
+    let proof_bytes = slice_to_js_array_buffer!(&proof.to_bytes()[..], cx);
+    let timestamp_bytes = slice_to_js_array_buffer!(&timestamp_bytes[..], cx);
+    result.set(&mut cx, "proof", proof_bytes)?;
+    result.set(&mut cx, "timestamp", timestamp_bytes)?;
+    Ok(result)
+}
+
+/// @param ArrayBuffer `proof` - The proof to verify
+/// @param ArrayBuffer `publicKey` - The public key of the issuer that signed the token behind this proof
+/// @param ArrayBuffer `id` - The identifier the proof was generated for
+/// @param ArrayBuffer `nonce` - The nonce the proof was bound to when it was created
+/// @returns boolean - true if the proof is valid for the given public key, id, and nonce
+fn verify_proof(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let proof_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let pk_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(2)?;
+    let nonce_buffer: Handle<JsArrayBuffer> = cx.argument(3)?;
+
+    let proof_bytes = proof_buffer.as_slice(&cx);
+    let pk_bytes = pk_buffer.as_slice(&cx);
+    let id_bytes = id_buffer.as_slice(&cx);
+    let nonce_bytes = nonce_buffer.as_slice(&cx);
+
+    let is_valid = core::verify_proof(proof_bytes, pk_bytes, id_bytes, nonce_bytes).map_err(|e| to_throw(&mut cx, e))?;
+
+    Ok(cx.boolean(is_valid))
+}
+
+/// Verifies a proof whose nonce is a big-endian microsecond timestamp, rejecting it
+/// outright if that timestamp falls outside `[now - maxSkewMicros, now + maxSkewMicros]`.
+/// This bounds replay of a presented token to a validity window around the current time.
+/// @param ArrayBuffer `proof` - The proof to verify
+/// @param ArrayBuffer `publicKey` - The public key of the issuer that signed the token behind this proof
+/// @param ArrayBuffer `id` - The identifier the proof was generated for
+/// @param ArrayBuffer `timestamp` - The big-endian microsecond timestamp the proof was bound to
+/// @param number `maxSkewMicros` - The maximum allowed distance, in microseconds, between `timestamp` and now
+/// @returns boolean - true if `timestamp` is within the allowed window and the proof is valid
+fn verify_proof_timestamp(mut cx: FunctionContext) -> JsResult<JsBoolean> {
+    let proof_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let pk_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(2)?;
+    let timestamp_buffer: Handle<JsArrayBuffer> = cx.argument(3)?;
+    let max_skew_micros = cx.argument::<JsNumber>(4)?.value(&mut cx) as u128;
+
+    let timestamp_bytes = timestamp_buffer.as_slice(&cx);
+    if timestamp_bytes.len() != 16 {
+        return cx.throw_error("timestamp must be 16 bytes");
+    }
+    let timestamp = u128::from_be_bytes(<[u8; 16]>::try_from(timestamp_bytes).unwrap());
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_micros();
+    let lower = now.saturating_sub(max_skew_micros);
+    let upper = now.saturating_add(max_skew_micros);
+    if timestamp < lower || timestamp > upper {
+        return Ok(cx.boolean(false));
+    }
+
+    let proof_bytes = proof_buffer.as_slice(&cx);
+    let pk_bytes = pk_buffer.as_slice(&cx);
+    let id_bytes = id_buffer.as_slice(&cx);
+
+    let is_valid = core::verify_proof(proof_bytes, pk_bytes, id_bytes, timestamp_bytes).map_err(|e| to_throw(&mut cx, e))?;
+
+    Ok(cx.boolean(is_valid))
+}
+
+/// Verifies a batch of proofs, one `{ proof, publicKey, id, nonce }` object per item.
+///
+/// Folds every item's pairing check into a single randomized-scalar aggregate
+/// Miller loop (see `crate::aggregate_verify`) instead of one pairing per item,
+/// so a server validating many tokens at once pays one final exponentiation
+/// rather than N. If the aggregate fails - because at least one item is
+/// malformed or invalid - this falls back to `verifyProof` per item to find out
+/// which index(es) actually failed; a malformed item (wrong lengths,
+/// unparseable bytes) is recorded as a failure at its index rather than
+/// aborting the rest of the batch.
+/// @param Array<{proof: ArrayBuffer, publicKey: ArrayBuffer, id: ArrayBuffer, nonce: ArrayBuffer}> `items`
+/// @returns {
+///     "allValid": boolean,
+///     "failures": number[]
+/// }
+fn verify_proofs_batch(mut cx: FunctionContext) -> JsResult<JsObject> {
+    let items: Handle<JsArray> = cx.argument(0)?;
+    let items_vec: Vec<Handle<JsValue>> = items.to_vec(&mut cx)?;
+
+    let mut owned_items = Vec::with_capacity(items_vec.len());
+    for item in items_vec {
+        let obj = item.downcast::<JsObject, _>(&mut cx).or_throw(&mut cx)?;
+
+        let proof_buffer: Handle<JsArrayBuffer> = obj.get(&mut cx, "proof")?;
+        let pk_buffer: Handle<JsArrayBuffer> = obj.get(&mut cx, "publicKey")?;
+        let id_buffer: Handle<JsArrayBuffer> = obj.get(&mut cx, "id")?;
+        let nonce_buffer: Handle<JsArrayBuffer> = obj.get(&mut cx, "nonce")?;
+
+        let proof_bytes = proof_buffer.as_slice(&cx).to_vec();
+        let pk_bytes = pk_buffer.as_slice(&cx).to_vec();
+        let id_bytes = id_buffer.as_slice(&cx).to_vec();
+        let nonce_bytes = nonce_buffer.as_slice(&cx).to_vec();
+
+        owned_items.push((proof_bytes, pk_bytes, id_bytes, nonce_bytes));
+    }
+
+    let aggregate_input: Vec<crate::aggregate_verify::BatchItem> = owned_items
+        .iter()
+        .map(|(proof, pk, id, nonce)| (proof.as_slice(), pk.as_slice(), id.as_slice(), nonce.as_slice()))
+        .collect();
+
+    let failures = match crate::aggregate_verify::verify_aggregate(&aggregate_input) {
+        Ok(true) => Vec::new(),
+        Ok(false) | Err(_) => owned_items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (proof, pk, id, nonce))| match core::verify_proof(proof, pk, id, nonce) {
+                Ok(true) => None,
+                Ok(false) | Err(_) => Some(i),
+            })
+            .collect(),
+    };
+
+    let result = JsObject::new(&mut cx);
+    let all_valid = cx.boolean(failures.is_empty());
+    let failures_arr = JsArray::new(&mut cx, failures.len() as u32);
+    for (i, idx) in failures.iter().enumerate() {
+        let n = cx.number(*idx as f64);
+        failures_arr.set(&mut cx, i as u32, n)?;
+    }
+    result.set(&mut cx, "allValid", all_valid)?;
+    result.set(&mut cx, "failures", failures_arr)?;
+    Ok(result)
+}
+
+/// Async variant of `newToken` that runs the signing work on `background_pool()`
+/// instead of the libuv main thread, so a server minting many tokens doesn't
+/// stall its event loop on the underlying elliptic-curve math. Returns a
+/// `Promise` that settles on `channel` once the background work completes.
+/// @param ArrayBuffer `id` - The identifier to use for this token
+/// @param ArrayBuffer `secretKey` - The secret key used for signing this token
+/// @returns Promise<{ token: ArrayBuffer }>
+fn new_token_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let sk_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+
+    let id_bytes = id_buffer.as_slice(&cx).to_vec();
+    let sk_bytes = sk_buffer.as_slice(&cx).to_vec();
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    background_pool().execute(move || {
+        let outcome = core::build_token(&id_bytes, &sk_bytes).map(|token| token.to_bytes());
+        deferred.settle_with(&channel, move |mut cx| match outcome {
+            Ok(token_bytes) => {
+                let token_bytes = slice_to_js_array_buffer!(&token_bytes, cx);
+                let result = JsObject::new(&mut cx);
+                result.set(&mut cx, "token", token_bytes)?;
+                Ok(result)
+            }
+            Err(_) => cx.throw_error("failed to create token"),
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Async variant of `newProofTimestamp`, offloading proof generation to
+/// `background_pool()`. See `newTokenAsync` for how the `Promise` is settled.
+/// @param ArrayBuffer `token` - The token or blinded token for which to generate a proof
+/// @param ArrayBuffer `id` - The identifier to use for this token
+/// @param Array<ArrayBuffer> `blindings` - All the blindings applied to the token
+/// @returns Promise<{ proof: ArrayBuffer, timestamp: ArrayBuffer }>
+fn new_proof_timestamp_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let token_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let blindings_vec = obj_field_to_vec!(cx, 2);
+
+    let blindings = decode_blindings(&mut cx, blindings_vec)?;
+    let token_bytes = token_buffer.as_slice(&cx).to_vec();
+    let id_bytes = id_buffer.as_slice(&cx).to_vec();
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    background_pool().execute(move || {
+        let outcome: Result<_, CoreError> = (|| {
+            let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            let timestamp_bytes = timestamp.as_micros().to_be_bytes();
+            let proof = core::build_proof(&token_bytes, blindings.as_slice(), &id_bytes, &timestamp_bytes[..])?;
+            Ok((proof.to_bytes(), timestamp_bytes))
+        })();
+
+        deferred.settle_with(&channel, move |mut cx| match outcome {
+            Ok((proof_bytes, timestamp_bytes)) => {
+                let proof_buffer = slice_to_js_array_buffer!(&proof_bytes[..], cx);
+                let timestamp_buffer = slice_to_js_array_buffer!(&timestamp_bytes[..], cx);
+                let result = JsObject::new(&mut cx);
+                result.set(&mut cx, "proof", proof_buffer)?;
+                result.set(&mut cx, "timestamp", timestamp_buffer)?;
+                Ok(result)
+            }
+            Err(_) => cx.throw_error("failed to generate proof"),
+        });
+    });
+
+    Ok(promise)
+}
+
+/// Async variant of `verifyProof`, offloading the pairing check to
+/// `background_pool()`. See `newTokenAsync` for how the `Promise` is settled.
+/// @param ArrayBuffer `proof` - The proof to verify
+/// @param ArrayBuffer `publicKey` - The public key of the issuer that signed the token behind this proof
+/// @param ArrayBuffer `id` - The identifier the proof was generated for
+/// @param ArrayBuffer `nonce` - The nonce the proof was bound to when it was created
+/// @returns Promise<boolean>
+fn verify_proof_async(mut cx: FunctionContext) -> JsResult<JsPromise> {
+    let proof_buffer: Handle<JsArrayBuffer> = cx.argument(0)?;
+    let pk_buffer: Handle<JsArrayBuffer> = cx.argument(1)?;
+    let id_buffer: Handle<JsArrayBuffer> = cx.argument(2)?;
+    let nonce_buffer: Handle<JsArrayBuffer> = cx.argument(3)?;
+
+    let proof_bytes = proof_buffer.as_slice(&cx).to_vec();
+    let pk_bytes = pk_buffer.as_slice(&cx).to_vec();
+    let id_bytes = id_buffer.as_slice(&cx).to_vec();
+    let nonce_bytes = nonce_buffer.as_slice(&cx).to_vec();
+    let channel = cx.channel();
+    let (deferred, promise) = cx.promise();
+
+    background_pool().execute(move || {
+        let outcome = core::verify_proof(&proof_bytes, &pk_bytes, &id_bytes, &nonce_bytes);
+        deferred.settle_with(&channel, move |mut cx| match outcome {
+            Ok(is_valid) => Ok(cx.boolean(is_valid)),
+            Err(_) => cx.throw_error("malformed proof or public key"),
+        });
+    });
+
+    Ok(promise)
+}
+
+register_module!(mut cx, {
+    cx.export_function("newKeys", new_keys)?;
+    cx.export_function("newBlinding", new_blinding)?;
+    cx.export_function("newToken", new_token)?;
+    cx.export_function("blindToken", blind_token)?;
+    cx.export_function("newProof", new_proof)?;
+    cx.export_function("newProofTimestamp", new_proof_timestamp)?;
+    cx.export_function("newTokenAsync", new_token_async)?;
+    cx.export_function("newProofTimestampAsync", new_proof_timestamp_async)?;
+    cx.export_function("verifyProofAsync", verify_proof_async)?;
+    cx.export_function("verifyProof", verify_proof)?;
+    cx.export_function("verifyProofTimestamp", verify_proof_timestamp)?;
+    cx.export_function("encode", crate::encoding::encode)?;
+    cx.export_function("decode", crate::encoding::decode)?;
+    cx.export_function("verifyProofsBatch", verify_proofs_batch)?;
+    Ok(())
+});
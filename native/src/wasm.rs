@@ -0,0 +1,108 @@
+//! `wasm-bindgen` front-end for the Oberon token scheme, mirroring the Neon
+//! addon's surface (`newKeys`, `newBlinding`, `newToken`, `blindToken`,
+//! `newProof`, `verifyProof`) so client-side blinding works in a browser or
+//! any other JS runtime without a native toolchain. All the signing/proof
+//! logic lives in `crate::core`; this module only translates to and from
+//! `Uint8Array`.
+
+use js_sys::{Object, Reflect, Uint8Array};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::core::{self, CoreError};
+
+fn core_err_to_js(err: CoreError) -> JsValue {
+    JsValue::from_str(match err {
+        CoreError::InvalidLength => "invalid argument length",
+        CoreError::InvalidInput => "invalid argument",
+    })
+}
+
+fn set(obj: &Object, key: &str, value: impl Into<JsValue>) -> Result<(), JsValue> {
+    Reflect::set(obj, &JsValue::from_str(key), &value.into()).map(|_| ())
+}
+
+fn decode_blindings(blindings: &js_sys::Array) -> Result<Vec<oberon::Blinding>, JsValue> {
+    blindings
+        .iter()
+        .map(|item| {
+            let bytes: Uint8Array = item
+                .dyn_into()
+                .map_err(|_| JsValue::from_str("blindings must be an array of Uint8Array"))?;
+            core::decode_blinding(&bytes.to_vec()).map_err(core_err_to_js)
+        })
+        .collect()
+}
+
+/// @param seed - An optional seed to create an oberon key pair
+/// @returns `{ secretKey: Uint8Array, publicKey: Uint8Array }`
+#[wasm_bindgen(js_name = newKeys)]
+pub fn new_keys(seed: Option<Vec<u8>>) -> Result<JsValue, JsValue> {
+    let (sk_bytes, pk_bytes) = core::generate_keys(seed.as_deref());
+
+    let result = Object::new();
+    set(&result, "secretKey", Uint8Array::from(sk_bytes.as_slice()))?;
+    set(&result, "publicKey", Uint8Array::from(pk_bytes.as_slice()))?;
+    Ok(result.into())
+}
+
+/// @param blinding - A byte array for a blinding factor
+/// @returns `{ blinding: Uint8Array }`
+#[wasm_bindgen(js_name = newBlinding)]
+pub fn new_blinding(blinding: Vec<u8>) -> Result<JsValue, JsValue> {
+    let blinding_bytes = core::generate_blinding(&blinding);
+
+    let result = Object::new();
+    set(&result, "blinding", Uint8Array::from(blinding_bytes.as_slice()))?;
+    Ok(result.into())
+}
+
+/// @param id - The identifier to use for this token
+/// @param secretKey - The secret key used for signing this token
+/// @returns `{ token: Uint8Array }`
+#[wasm_bindgen(js_name = newToken)]
+pub fn new_token(id: Vec<u8>, secret_key: Vec<u8>) -> Result<JsValue, JsValue> {
+    let token = core::build_token(&id, &secret_key).map_err(core_err_to_js)?;
+
+    let result = Object::new();
+    set(&result, "token", Uint8Array::from(&token.to_bytes()[..]))?;
+    Ok(result.into())
+}
+
+/// @param token - The token or blinded token signed by the issuing authority
+/// @param blinding - The blinding factor to apply to the token
+/// @returns `{ token: Uint8Array }`
+#[wasm_bindgen(js_name = blindToken)]
+pub fn blind_token(token: Vec<u8>, blinding: Vec<u8>) -> Result<JsValue, JsValue> {
+    let blinded_token_bytes = core::blind_token(&token, &blinding).map_err(core_err_to_js)?;
+
+    let result = Object::new();
+    set(&result, "token", Uint8Array::from(blinded_token_bytes.as_slice()))?;
+    Ok(result.into())
+}
+
+/// @param token - The token or blinded token for which to generate a proof
+/// @param id - The identifier to use for this token
+/// @param blindings - All the blindings applied to the token
+/// @param nonce - The nonce to bind this proof to
+/// @returns `{ proof: Uint8Array }`
+#[wasm_bindgen(js_name = newProof)]
+pub fn new_proof(token: Vec<u8>, id: Vec<u8>, blindings: js_sys::Array, nonce: Vec<u8>) -> Result<JsValue, JsValue> {
+    let blindings = decode_blindings(&blindings)?;
+
+    let proof = core::build_proof(&token, &blindings, &id, &nonce).map_err(core_err_to_js)?;
+
+    let result = Object::new();
+    set(&result, "proof", Uint8Array::from(&proof.to_bytes()[..]))?;
+    Ok(result.into())
+}
+
+/// @param proof - The proof to verify
+/// @param publicKey - The public key of the issuer that signed the token behind this proof
+/// @param id - The identifier the proof was generated for
+/// @param nonce - The nonce the proof was bound to when it was created
+/// @returns true if the proof is valid for the given public key, id, and nonce
+#[wasm_bindgen(js_name = verifyProof)]
+pub fn verify_proof(proof: Vec<u8>, public_key: Vec<u8>, id: Vec<u8>, nonce: Vec<u8>) -> Result<bool, JsValue> {
+    core::verify_proof(&proof, &public_key, &id, &nonce).map_err(core_err_to_js)
+}